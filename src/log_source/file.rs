@@ -0,0 +1,53 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+};
+
+use log::trace;
+
+use crate::NasResult;
+
+use super::LogSource;
+
+pub(super) const DEFAULT_PATH: &str = "/var/log/auth.log";
+
+/// Tails a plain-text log file (e.g. `/var/log/auth.log`), remembering a byte offset as its
+/// resume point.
+pub struct FileLogSource {
+    path: String,
+    pos: u64,
+    first_loop: bool,
+}
+
+impl FileLogSource {
+    pub fn new(path: String) -> Self {
+        FileLogSource {
+            path,
+            pos: 0,
+            first_loop: true,
+        }
+    }
+}
+
+impl LogSource for FileLogSource {
+    fn poll(&mut self) -> NasResult<Vec<String>> {
+        trace!("log file pos: {}", self.pos);
+        let mut file = File::open(&self.path)?;
+        // Skip all existing lines if this is the first poll.
+        if self.first_loop {
+            trace!("first loop, skipping to end of {}", self.path);
+            self.pos = file.metadata()?.len();
+            self.first_loop = false;
+        }
+        if file.metadata()?.len() < self.pos {
+            trace!("file length is less than pos, setting to zero");
+            self.pos = 0;
+        }
+        file.seek(SeekFrom::Start(self.pos))?;
+        let mut new_lines = String::new();
+        let bytes_read = file.read_to_string(&mut new_lines)?;
+        self.pos += bytes_read as u64;
+        trace!("bytes_read: {}", bytes_read);
+        Ok(new_lines.lines().map(str::to_owned).collect())
+    }
+}
@@ -0,0 +1,27 @@
+mod file;
+mod journald;
+
+use crate::NasResult;
+
+pub use file::FileLogSource;
+pub use journald::JournaldLogSource;
+
+/// A source of new login-related log lines to scan for login events.
+pub trait LogSource {
+    /// Return any new lines since the last poll. The first call skips existing content and
+    /// returns nothing, mirroring `tail -f` semantics.
+    fn poll(&mut self) -> NasResult<Vec<String>>;
+}
+
+/// Build the configured [`LogSource`]. `kind` is `"file"` or `"journald"`; `path_override`
+/// overrides the log file path or journald unit name, respectively.
+pub fn build_log_source(kind: &str, path_override: Option<&str>) -> Box<dyn LogSource> {
+    match kind {
+        "journald" => Box::new(JournaldLogSource::new(
+            path_override.unwrap_or("ssh").to_string(),
+        )),
+        _ => Box::new(FileLogSource::new(
+            path_override.unwrap_or(file::DEFAULT_PATH).to_string(),
+        )),
+    }
+}
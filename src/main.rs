@@ -1,40 +1,158 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     error::Error,
-    io::{Read, Seek},
-    net::Ipv4Addr,
-    time::Duration,
+    net::{IpAddr, Ipv6Addr},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::{Duration, SystemTime},
 };
 
 use libzetta::zpool::{Health, ZpoolEngine, ZpoolOpen3};
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 use serde::Deserialize;
+use signal_hook::consts::SIGHUP;
 use simple_logger::SimpleLogger;
 
-type NasResult<T> = Result<T, Box<dyn Error>>;
+mod log_source;
+mod notifier;
+mod templates;
+
+use log_source::{build_log_source, LogSource};
+use notifier::{build_notifiers, Notifier, NotifierConfig};
+use templates::TemplatesConfig;
+
+pub(crate) type NasResult<T> = Result<T, Box<dyn Error>>;
 
 struct NasNotifier {
     config: Config,
-    auth_log_pos: u64,
+    log_source: Box<dyn LogSource>,
     zpools_health: HashMap<String, Health>,
-    first_loop: bool,
+    zpools_pending: HashMap<String, PendingHealth>,
+    failed_logins: FailedLoginWindow,
+    notify_state: Arc<Mutex<Arc<NotifyState>>>,
+    notify_tx: mpsc::Sender<String>,
+}
+
+/// What the background delivery thread (spawned in `NasNotifier::new`) needs to send an alert:
+/// the configured channels and how to retry them. Held behind `Arc<Mutex<Arc<_>>>` so a config
+/// reload can swap in a new snapshot (a quick lock) without making the delivery thread hold the
+/// lock for the whole, possibly multi-minute, retry loop.
+struct NotifyState {
+    notifiers: Vec<Box<dyn Notifier>>,
+    retry: RetryConfig,
+}
+
+/// Fan an alert out to every configured notifier channel, retrying each with exponential backoff.
+/// A channel that still fails after exhausting its retries only logs a warning; it does not stop
+/// the other channels.
+fn deliver_notification(notifiers: &[Box<dyn Notifier>], retry: &RetryConfig, text: &str) {
+    let max_attempts = retry.max_attempts.unwrap_or(5).max(1);
+    let base_delay = Duration::from_millis(retry.base_delay_ms.unwrap_or(1000));
+    let max_delay = Duration::from_millis(retry.max_delay_ms.unwrap_or(60_000));
+    for notifier in notifiers {
+        let mut delay = base_delay;
+        let mut last_err = None;
+        for attempt in 1..=max_attempts {
+            match notifier.send(text) {
+                Ok(()) => {
+                    last_err = None;
+                    break;
+                }
+                Err(e) => {
+                    debug!(
+                        "Attempt {}/{} to send via '{}' failed: {}",
+                        attempt,
+                        max_attempts,
+                        notifier.name(),
+                        e
+                    );
+                    last_err = Some(e);
+                    if attempt < max_attempts {
+                        std::thread::sleep(delay);
+                        delay = (delay * 2).min(max_delay);
+                    }
+                }
+            }
+        }
+        if let Some(e) = last_err {
+            warn!(
+                "Giving up on '{}' after {} attempt(s), alert not delivered: {}",
+                notifier.name(),
+                max_attempts,
+                e
+            );
+        }
+    }
+}
+
+/// A zpool health reading that has been observed but hasn't persisted for `confirm-seconds` yet,
+/// so it hasn't been promoted to `zpools_health` or notified on.
+struct PendingHealth {
+    state: Health,
+    first_seen: SystemTime,
+}
+
+/// Coalesces failed login attempts into a single summary notification instead of one message
+/// per line. The window opens at the first recorded attempt and closes once it has lasted the
+/// configured length; `reset` starts the next window at the following attempt.
+#[derive(Default)]
+struct FailedLoginWindow {
+    count: u64,
+    ips: HashSet<String>,
+    window_start: Option<SystemTime>,
+}
+
+impl FailedLoginWindow {
+    fn record(&mut self, ip: Option<IpAddr>, now: SystemTime) {
+        if self.window_start.is_none() {
+            self.window_start = Some(now);
+        }
+        self.count += 1;
+        if let Some(ip) = ip {
+            self.ips.insert(ip.to_string());
+        }
+    }
+    fn should_flush(&self, window: Duration, now: SystemTime) -> bool {
+        match self.window_start {
+            Some(start) => now.duration_since(start).unwrap_or_default() >= window,
+            None => false,
+        }
+    }
+    fn reset(&mut self) {
+        self.count = 0;
+        self.ips.clear();
+        self.window_start = None;
+    }
 }
 
 #[derive(Deserialize)]
 struct Config {
     #[serde(rename = "poll-duration-seconds")]
     poll_duration_seconds: u64,
-    telegram: TelegramConfig,
+    hostname: String,
+    notifiers: Vec<NotifierConfig>,
+    #[serde(rename = "log-source")]
+    log_source: Option<String>,
+    #[serde(rename = "log-source-path")]
+    log_source_path: Option<String>,
     notifications: NotificationsConfig,
+    #[serde(default)]
+    templates: TemplatesConfig,
+    #[serde(default)]
+    retry: RetryConfig,
 }
 
-#[derive(Deserialize)]
-struct TelegramConfig {
-    #[serde(rename = "user-id")]
-    user_id: i64,
-    hostname: String,
-    #[serde(rename = "api-key")]
-    api_key: String,
+/// Bounded retry with exponential backoff for notification delivery.
+#[derive(Deserialize, Clone, Default)]
+struct RetryConfig {
+    #[serde(rename = "max-attempts")]
+    max_attempts: Option<u32>,
+    #[serde(rename = "base-delay-ms")]
+    base_delay_ms: Option<u64>,
+    #[serde(rename = "max-delay-ms")]
+    max_delay_ms: Option<u64>,
 }
 
 #[derive(Deserialize, Clone)]
@@ -47,6 +165,10 @@ struct NotificationsConfig {
     failed_login: Option<bool>,
     #[serde(rename = "pool-health")]
     pool_health: Option<bool>,
+    #[serde(rename = "pool-confirm-seconds")]
+    pool_confirm_seconds: Option<u64>,
+    #[serde(rename = "failed-login-window-seconds")]
+    failed_login_window_seconds: Option<u64>,
 }
 
 fn zpool_health_to_string(health: &Health) -> String {
@@ -61,105 +183,212 @@ fn zpool_health_to_string(health: &Health) -> String {
     }
 }
 
-impl Default for NasNotifier {
-    fn default() -> Self {
-        NasNotifier {
-            config: Config {
-                poll_duration_seconds: 30,
-                telegram: TelegramConfig {
-                    user_id: 0,
-                    hostname: String::new(),
-                    api_key: String::new(),
-                },
-                notifications: NotificationsConfig {
-                    new_login_ip: None,
-                    known_ips: None,
-                    failed_login: None,
-                    pool_health: None,
-                },
-            },
-            auth_log_pos: 0,
-            zpools_health: HashMap::new(),
-            first_loop: true,
+/// Whether `health` represents a pool that's back to normal operation.
+fn is_recovered_health(health: &Health) -> bool {
+    matches!(health, Health::Online | Health::Available)
+}
+
+fn extract_ip_addresses(line: &str) -> Vec<IpAddr> {
+    line.split_ascii_whitespace()
+        .filter_map(|w| w.parse().ok())
+        .collect()
+}
+
+/// Whether `ip` is confined to a single host or link and so isn't worth reporting as a new
+/// login source: private/loopback/link-local for IPv4, and unique-local/loopback/link-local for
+/// IPv6.
+fn is_locally_scoped(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local(),
+        IpAddr::V6(v6) => v6.is_loopback() || is_unique_local(v6) || is_unicast_link_local(v6),
+    }
+}
+
+/// `fc00::/7`, the IPv6 equivalent of RFC 1918 private addressing.
+fn is_unique_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xfe00) == 0xfc00
+}
+
+/// `fe80::/10`.
+fn is_unicast_link_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+/// Apply one zpool health reading to `confirmed`/`pending`, implementing the confirm-seconds
+/// debounce: a reading that differs from the last confirmed state is tracked as pending and is
+/// only promoted (updating `confirmed` and returning `Some((old_state, new_state))`) once it has
+/// persisted for at least `confirm_duration`. A reading that reverts to the confirmed state
+/// before that clears the pending entry. `now` is passed in rather than read from `SystemTime`
+/// so the promotion logic can be driven deterministically in tests.
+fn process_zpool_reading(
+    confirmed: &mut HashMap<String, Health>,
+    pending: &mut HashMap<String, PendingHealth>,
+    name: &str,
+    current_state: &Health,
+    now: SystemTime,
+    confirm_duration: Duration,
+) -> Option<(Health, Health)> {
+    match confirmed.get(name) {
+        None => {
+            confirmed.insert(name.to_owned(), current_state.to_owned());
+            None
+        }
+        Some(confirmed_state) if confirmed_state == current_state => {
+            pending.remove(name);
+            None
+        }
+        Some(confirmed_state) => {
+            let confirmed_state = confirmed_state.to_owned();
+            let persisted_since = match pending.get(name) {
+                Some(p) if p.state == *current_state => {
+                    now.duration_since(p.first_seen).unwrap_or_default()
+                }
+                _ => {
+                    pending.insert(
+                        name.to_owned(),
+                        PendingHealth {
+                            state: current_state.to_owned(),
+                            first_seen: now,
+                        },
+                    );
+                    Duration::ZERO
+                }
+            };
+            if persisted_since >= confirm_duration {
+                confirmed.insert(name.to_owned(), current_state.to_owned());
+                pending.remove(name);
+                Some((confirmed_state, current_state.to_owned()))
+            } else {
+                None
+            }
         }
     }
 }
 
 impl NasNotifier {
     const CONFIG_FILE_PATH: &str = "/etc/nas-notifier.toml";
-    const AUTH_LOG_FILE_PATH: &str = "/var/log/auth.log";
 
     fn new() -> NasResult<Self> {
         SimpleLogger::new().env().without_timestamps().init()?;
+        let config = Self::read_config()?;
+        let notifiers = build_notifiers(&config.notifiers)?;
+        let log_source = build_log_source(
+            config.log_source.as_deref().unwrap_or("file"),
+            config.log_source_path.as_deref(),
+        );
+        let notify_state = Arc::new(Mutex::new(Arc::new(NotifyState {
+            notifiers,
+            retry: config.retry.clone(),
+        })));
+        let (notify_tx, notify_rx) = mpsc::channel::<String>();
+        let worker_state = Arc::clone(&notify_state);
+        std::thread::spawn(move || {
+            for text in notify_rx {
+                // Only hold the lock long enough to grab the current snapshot, so a slow/failing
+                // channel's retry loop below doesn't block a concurrent config reload.
+                let state = Arc::clone(&worker_state.lock().unwrap_or_else(|e| e.into_inner()));
+                deliver_notification(&state.notifiers, &state.retry, &text);
+            }
+        });
+        Ok(NasNotifier {
+            config,
+            log_source,
+            zpools_health: HashMap::new(),
+            zpools_pending: HashMap::new(),
+            failed_logins: FailedLoginWindow::default(),
+            notify_state,
+            notify_tx,
+        })
+    }
+    fn read_config() -> NasResult<Config> {
         debug!("Reading config file at {}", Self::CONFIG_FILE_PATH);
         let config_file = std::fs::read_to_string(Self::CONFIG_FILE_PATH)?;
         let config: Config = toml::from_str(&config_file)?;
         debug!("Successfully read and parsed config file");
-        Ok(NasNotifier {
-            config,
-            ..Default::default()
-        })
+        Ok(config)
+    }
+    /// Re-read and re-parse the config file, swapping it in if it's valid. The log source's
+    /// resume point, `zpools_health`, and in-flight debounce state are left untouched so
+    /// in-progress tracking survives the reload.
+    fn reload_config(&mut self) -> NasResult<()> {
+        let config = Self::read_config()?;
+        let notifiers = build_notifiers(&config.notifiers)?;
+        let new_state = Arc::new(NotifyState {
+            notifiers,
+            retry: config.retry.clone(),
+        });
+        *self
+            .notify_state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = new_state;
+        self.config = config;
+        Ok(())
     }
     fn run(mut self) -> NasResult<()> {
-        let new_login_ip = self.config.notifications.new_login_ip.unwrap_or(false);
-        let known_ips = self
-            .config
-            .notifications
-            .known_ips
-            .clone()
-            .unwrap_or_default();
-        let failed_login = self.config.notifications.failed_login.unwrap_or(false);
-        let pool_unhealthy = self.config.notifications.pool_health.unwrap_or(false);
+        let reload_requested = Arc::new(AtomicBool::new(false));
+        signal_hook::flag::register(SIGHUP, Arc::clone(&reload_requested))?;
         let zfs_handle = ZpoolOpen3::default();
         info!("Startup complete, beginning polling loop");
 
         // Periodically poll all data sources, process them, and send notifications as needed.
         loop {
             debug!("New polling loop");
-            if new_login_ip || failed_login {
-                // Get new lines from /var/log/auth.log.
-                debug!("Getting new lines from {}", Self::AUTH_LOG_FILE_PATH);
-                trace!("auth_log_pos: {}", self.auth_log_pos);
-                let mut auth_log = std::fs::File::open(Self::AUTH_LOG_FILE_PATH)?;
-                // Skip all existing lines if this is the first loop
-                if self.first_loop {
-                    trace!("first loop, skipping to end of auth log");
-                    self.auth_log_pos = auth_log.metadata()?.len();
-                    self.first_loop = false;
+            if reload_requested.swap(false, Ordering::Relaxed) {
+                info!("Received SIGHUP, reloading config from {}", Self::CONFIG_FILE_PATH);
+                match self.reload_config() {
+                    Ok(()) => info!("Config reloaded successfully"),
+                    Err(e) => warn!("Failed to reload config, keeping previous config: {}", e),
                 }
-                if auth_log.metadata()?.len() < self.auth_log_pos {
-                    trace!("file length is less than auth_log_pos, setting to zero");
-                    self.auth_log_pos = 0;
-                }
-                auth_log.seek(std::io::SeekFrom::Start(self.auth_log_pos))?;
-                let mut new_auth_lines = String::new();
-                let bytes_read = auth_log.read_to_string(&mut new_auth_lines)?;
-                self.auth_log_pos += bytes_read as u64;
-                trace!("bytes_read: {}", bytes_read);
-                trace!("new_auth_lines.len(): {}", new_auth_lines.len());
-                trace!("auth_log_pos: {}", self.auth_log_pos);
-
-                // Parse new lines in auth.log and send notifications as needed.
-                for new_line in new_auth_lines.lines() {
+            }
+            let new_login_ip = self.config.notifications.new_login_ip.unwrap_or(false);
+            let known_ips: Vec<IpAddr> = self
+                .config
+                .notifications
+                .known_ips
+                .iter()
+                .flatten()
+                .filter_map(|s| s.parse().ok())
+                .collect();
+            let failed_login = self.config.notifications.failed_login.unwrap_or(false);
+            let pool_unhealthy = self.config.notifications.pool_health.unwrap_or(false);
+            let pool_confirm_duration = Duration::from_secs(
+                self.config.notifications.pool_confirm_seconds.unwrap_or(0),
+            );
+            let failed_login_window = Duration::from_secs(
+                self.config
+                    .notifications
+                    .failed_login_window_seconds
+                    .unwrap_or(60),
+            );
+            if new_login_ip || failed_login {
+                debug!("Getting new lines from the log source");
+                // A single bad poll (e.g. a transient `journalctl` failure) shouldn't take down
+                // the whole daemon; treat it like an empty read and try again next time.
+                let new_lines = match self.log_source.poll() {
+                    Ok(lines) => lines,
+                    Err(e) => {
+                        warn!("Failed to poll log source, skipping this cycle: {}", e);
+                        Vec::new()
+                    }
+                };
+                trace!("new_lines.len(): {}", new_lines.len());
+
+                // Parse new lines and send notifications as needed.
+                for new_line in &new_lines {
                     if new_login_ip
                         && new_line.contains("sshd")
                         && new_line.contains("Accepted publickey for")
                     {
-                        let ip_addresses: Vec<Ipv4Addr> = new_line
-                            .split_ascii_whitespace()
-                            .filter_map(|w| w.parse().ok())
-                            .collect();
-                        for ip in ip_addresses {
-                            if !ip.is_private() && !known_ips.contains(&ip.to_string()) {
+                        for ip in extract_ip_addresses(new_line) {
+                            if !is_locally_scoped(&ip) && !known_ips.contains(&ip) {
                                 info!("Found new login IP, sending notification");
-                                self.send_notification(
-                                    &format!("There was a successful login on `{}` from an unknown IP address. Here's the relevant line from `{}`:\n\n`{}`",
-                                        self.config.telegram.hostname,
-                                        Self::AUTH_LOG_FILE_PATH,
-                                        new_line.trim_end()
-                                    )
-                                )?;
-                                info!("Notification sent");
+                                let vars = HashMap::from([
+                                    ("hostname", self.config.hostname.clone()),
+                                    ("ip", ip.to_string()),
+                                    ("logline", new_line.trim_end().to_string()),
+                                ]);
+                                self.send_notification(&self.config.templates.new_login_ip(&vars));
+                                info!("Notification queued for delivery");
                             } else {
                                 debug!("Found new login, but the IP is private or whitelisted");
                             }
@@ -169,19 +398,36 @@ impl NasNotifier {
                         && new_line.contains("sshd")
                         && new_line.contains("Connection closed by authenticating user")
                     {
-                        info!("Found failed login, sending notification");
-                        self.send_notification(
-                            &format!("There was a failed login attempt on `{}`. Here's the relevant line from `{}`:\n\n`{}`",
-                                self.config.telegram.hostname,
-                                Self::AUTH_LOG_FILE_PATH,
-                                new_line.trim_end()
-                            )
-                        )?;
-                        info!("Notification sent");
+                        debug!("Found failed login, adding it to the current window");
+                        self.failed_logins.record(
+                            extract_ip_addresses(new_line).into_iter().next(),
+                            SystemTime::now(),
+                        );
                     }
                 }
             }
 
+            if failed_login
+                && self
+                    .failed_logins
+                    .should_flush(failed_login_window, SystemTime::now())
+            {
+                info!(
+                    "Failed login window elapsed with {} attempt(s) from {} IP(s), sending notification",
+                    self.failed_logins.count,
+                    self.failed_logins.ips.len()
+                );
+                let vars = HashMap::from([
+                    ("hostname", self.config.hostname.clone()),
+                    ("count", self.failed_logins.count.to_string()),
+                    ("ip_count", self.failed_logins.ips.len().to_string()),
+                    ("window_seconds", failed_login_window.as_secs().to_string()),
+                ]);
+                self.send_notification(&self.config.templates.failed_login(&vars));
+                self.failed_logins.reset();
+                info!("Notification queued for delivery");
+            }
+
             if pool_unhealthy {
                 // Check zpool health and send notifications for any changes in health status. Note
                 // that this does not clear destroyed zpools from memory. If this is an issue, then
@@ -191,28 +437,35 @@ impl NasNotifier {
                 debug!("Got zpool statuses");
                 for zpool in status {
                     let name = zpool.name().to_owned();
-                    if self.zpools_health.contains_key(&name) {
-                        // We already know about this zpool, so send a message if its health has
-                        // changed.
-                        let previous_state = self.zpools_health.get(&name).unwrap();
-                        let current_state = zpool.health();
-                        if previous_state != current_state {
-                            info!("Detected a zpool health status change for '{}' (new health status: '{}'), sending notification",
-                                name,
-                                zpool_health_to_string(current_state));
-                            self.send_notification(&format!(
-                                "Zpool `{}` entered the `{}` state.",
-                                name,
-                                zpool_health_to_string(current_state)
-                            ))?;
-                            info!("Notification sent");
-                            self.zpools_health.insert(name, current_state.to_owned());
-                        }
-                    } else {
+                    let current_state = zpool.health();
+                    if !self.zpools_health.contains_key(&name) {
                         // We haven't seen this zpool before, so add it to the hashmap.
                         info!("Found new zpool '{}'", name);
-                        let health = zpool.health().to_owned();
-                        self.zpools_health.insert(name, health);
+                    }
+                    let transition = process_zpool_reading(
+                        &mut self.zpools_health,
+                        &mut self.zpools_pending,
+                        &name,
+                        current_state,
+                        SystemTime::now(),
+                        pool_confirm_duration,
+                    );
+                    if let Some((old_state, new_state)) = transition {
+                        info!("Detected a zpool health status change for '{}' (new health status: '{}'), sending notification",
+                            name,
+                            zpool_health_to_string(&new_state));
+                        let vars = HashMap::from([
+                            ("pool", name.clone()),
+                            ("old_state", zpool_health_to_string(&old_state)),
+                            ("new_state", zpool_health_to_string(&new_state)),
+                        ]);
+                        let message = if is_recovered_health(&new_state) {
+                            self.config.templates.pool_recovered(&vars)
+                        } else {
+                            self.config.templates.pool_health_change(&vars)
+                        };
+                        self.send_notification(&message);
+                        info!("Notification queued for delivery");
                     }
                 }
             }
@@ -221,22 +474,210 @@ impl NasNotifier {
             std::thread::sleep(Duration::from_secs(self.config.poll_duration_seconds));
         }
     }
-    fn send_notification(&self, text: &str) -> NasResult<()> {
-        let url = format!(
-            "https://api.telegram.org/bot{}/sendMessage",
-            self.config.telegram.api_key
-        );
-        let mut payload = HashMap::new();
-        let user_id = self.config.telegram.user_id.to_string();
-        payload.insert("chat_id", user_id.as_str());
-        payload.insert("text", text);
-        payload.insert("parse_mode", "Markdown");
-        let client = reqwest::blocking::Client::new();
-        client.post(url).json(&payload).send()?;
-        Ok(())
+    /// Hand an alert off to the background delivery thread. Returns immediately; delivery
+    /// (including retries) happens off the polling loop, so a channel outage can't delay log
+    /// polling, pool checks, or config reloads.
+    fn send_notification(&self, text: &str) {
+        if self.notify_tx.send(text.to_owned()).is_err() {
+            warn!("Notification delivery thread is gone, alert not delivered: {}", text);
+        }
     }
 }
 
 fn main() -> NasResult<()> {
     NasNotifier::new()?.run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs_after(start: SystemTime, secs: u64) -> SystemTime {
+        start + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn failed_login_window_does_not_flush_before_start() {
+        let window = FailedLoginWindow::default();
+        assert!(!window.should_flush(Duration::from_secs(60), SystemTime::now()));
+    }
+
+    #[test]
+    fn failed_login_window_flushes_once_window_elapses() {
+        let start = SystemTime::now();
+        let mut window = FailedLoginWindow::default();
+        window.record(None, start);
+        window.record("203.0.113.1".parse().ok(), secs_after(start, 10));
+
+        assert_eq!(window.count, 2);
+        assert_eq!(window.ips.len(), 1);
+        assert!(!window.should_flush(Duration::from_secs(60), secs_after(start, 30)));
+        assert!(window.should_flush(Duration::from_secs(60), secs_after(start, 60)));
+
+        window.reset();
+        assert_eq!(window.count, 0);
+        assert!(window.ips.is_empty());
+        assert!(!window.should_flush(Duration::from_secs(60), secs_after(start, 120)));
+    }
+
+    #[test]
+    fn zpool_reading_is_confirmed_immediately_the_first_time() {
+        let mut confirmed = HashMap::new();
+        let mut pending = HashMap::new();
+        let transition = process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Online,
+            SystemTime::now(),
+            Duration::from_secs(60),
+        );
+        assert!(transition.is_none());
+        assert_eq!(
+            zpool_health_to_string(confirmed.get("tank").unwrap()),
+            "ONLINE"
+        );
+    }
+
+    #[test]
+    fn zpool_reading_is_not_promoted_before_confirm_duration_elapses() {
+        let start = SystemTime::now();
+        let mut confirmed = HashMap::from([("tank".to_string(), Health::Online)]);
+        let mut pending = HashMap::new();
+        let confirm_duration = Duration::from_secs(60);
+
+        let transition = process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Degraded,
+            start,
+            confirm_duration,
+        );
+        assert!(transition.is_none());
+        assert_eq!(
+            zpool_health_to_string(confirmed.get("tank").unwrap()),
+            "ONLINE"
+        );
+
+        let transition = process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Degraded,
+            secs_after(start, 30),
+            confirm_duration,
+        );
+        assert!(transition.is_none());
+    }
+
+    #[test]
+    fn zpool_reading_is_promoted_once_confirm_duration_elapses() {
+        let start = SystemTime::now();
+        let mut confirmed = HashMap::from([("tank".to_string(), Health::Online)]);
+        let mut pending = HashMap::new();
+        let confirm_duration = Duration::from_secs(60);
+
+        process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Degraded,
+            start,
+            confirm_duration,
+        );
+        let transition = process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Degraded,
+            secs_after(start, 60),
+            confirm_duration,
+        );
+
+        let (old_state, new_state) = transition.expect("should have been promoted");
+        assert_eq!(zpool_health_to_string(&old_state), "ONLINE");
+        assert_eq!(zpool_health_to_string(&new_state), "DEGRADED");
+        assert_eq!(
+            zpool_health_to_string(confirmed.get("tank").unwrap()),
+            "DEGRADED"
+        );
+        assert!(pending.get("tank").is_none());
+    }
+
+    #[test]
+    fn zpool_reading_reverting_before_confirmation_clears_pending() {
+        let start = SystemTime::now();
+        let mut confirmed = HashMap::from([("tank".to_string(), Health::Online)]);
+        let mut pending = HashMap::new();
+        let confirm_duration = Duration::from_secs(60);
+
+        process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Degraded,
+            start,
+            confirm_duration,
+        );
+        assert!(pending.contains_key("tank"));
+
+        let transition = process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Online,
+            secs_after(start, 30),
+            confirm_duration,
+        );
+        assert!(transition.is_none());
+        assert!(!pending.contains_key("tank"));
+    }
+
+    #[test]
+    fn zpool_reading_flapping_to_a_different_state_resets_the_timer() {
+        let start = SystemTime::now();
+        let mut confirmed = HashMap::from([("tank".to_string(), Health::Online)]);
+        let mut pending = HashMap::new();
+        let confirm_duration = Duration::from_secs(60);
+
+        process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Degraded,
+            start,
+            confirm_duration,
+        );
+        // Flaps to a different unhealthy state before confirming; the clock restarts.
+        process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Faulted,
+            secs_after(start, 50),
+            confirm_duration,
+        );
+        let transition = process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Faulted,
+            secs_after(start, 90),
+            confirm_duration,
+        );
+        assert!(transition.is_none());
+
+        let transition = process_zpool_reading(
+            &mut confirmed,
+            &mut pending,
+            "tank",
+            &Health::Faulted,
+            secs_after(start, 110),
+            confirm_duration,
+        );
+        let (old_state, new_state) = transition.expect("should have been promoted");
+        assert_eq!(zpool_health_to_string(&old_state), "ONLINE");
+        assert_eq!(zpool_health_to_string(&new_state), "FAULTED");
+    }
+}
@@ -0,0 +1,50 @@
+use lettre::{
+    transport::smtp::authentication::Credentials, Message, SmtpTransport, Transport,
+};
+use serde::Deserialize;
+
+use crate::NasResult;
+
+use super::Notifier;
+
+#[derive(Deserialize, Clone)]
+pub struct EmailConfig {
+    #[serde(rename = "smtp-host")]
+    smtp_host: String,
+    #[serde(rename = "smtp-user")]
+    smtp_user: String,
+    #[serde(rename = "smtp-password")]
+    smtp_password: String,
+    from: String,
+    to: String,
+}
+
+pub struct EmailNotifier {
+    config: EmailConfig,
+    transport: SmtpTransport,
+}
+
+impl EmailNotifier {
+    pub fn new(config: EmailConfig) -> NasResult<Self> {
+        let creds = Credentials::new(config.smtp_user.clone(), config.smtp_password.clone());
+        let transport = SmtpTransport::relay(&config.smtp_host)?
+            .credentials(creds)
+            .build();
+        Ok(EmailNotifier { config, transport })
+    }
+}
+
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &str {
+        "email"
+    }
+    fn send(&self, text: &str) -> NasResult<()> {
+        let message = Message::builder()
+            .from(self.config.from.parse()?)
+            .to(self.config.to.parse()?)
+            .subject("nas-notifier alert")
+            .body(text.to_owned())?;
+        self.transport.send(&message)?;
+        Ok(())
+    }
+}
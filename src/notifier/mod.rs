@@ -0,0 +1,66 @@
+mod email;
+mod slack;
+mod sns;
+mod telegram;
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::NasResult;
+
+pub use email::EmailConfig;
+pub use slack::SlackConfig;
+pub use sns::SnsConfig;
+pub use telegram::TelegramConfig;
+
+use email::EmailNotifier;
+use slack::SlackNotifier;
+use sns::SnsNotifier;
+use telegram::TelegramNotifier;
+
+/// Connect/read timeout used by the `reqwest`-based notifiers (Telegram and Slack), so a slow or
+/// dead endpoint can't block the fan-out indefinitely. The SNS and email notifiers use their own
+/// clients and do not apply this timeout.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A `reqwest::blocking::Client` configured with the shared HTTP notifier timeout.
+fn http_client() -> NasResult<reqwest::blocking::Client> {
+    Ok(reqwest::blocking::Client::builder()
+        .connect_timeout(HTTP_TIMEOUT)
+        .timeout(HTTP_TIMEOUT)
+        .build()?)
+}
+
+/// A channel that can deliver an alert message somewhere. `Send + Sync` so the configured
+/// notifiers can be shared with the background delivery thread (see `NasNotifier::new`).
+pub trait Notifier: Send + Sync {
+    /// A short, lowercase name for this channel, used in log messages.
+    fn name(&self) -> &str;
+    /// Deliver `text` to this channel.
+    fn send(&self, text: &str) -> NasResult<()>;
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum NotifierConfig {
+    Telegram(TelegramConfig),
+    Slack(SlackConfig),
+    Sns(SnsConfig),
+    Email(EmailConfig),
+}
+
+/// Build a concrete [`Notifier`] for each configured channel.
+pub fn build_notifiers(configs: &[NotifierConfig]) -> NasResult<Vec<Box<dyn Notifier>>> {
+    configs
+        .iter()
+        .map(|config| -> NasResult<Box<dyn Notifier>> {
+            Ok(match config {
+                NotifierConfig::Telegram(c) => Box::new(TelegramNotifier::new(c.clone())?),
+                NotifierConfig::Slack(c) => Box::new(SlackNotifier::new(c.clone())?),
+                NotifierConfig::Sns(c) => Box::new(SnsNotifier::new(c.clone())?),
+                NotifierConfig::Email(c) => Box::new(EmailNotifier::new(c.clone())?),
+            })
+        })
+        .collect()
+}
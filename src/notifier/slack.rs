@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::NasResult;
+
+use super::{http_client, Notifier};
+
+#[derive(Deserialize, Clone)]
+pub struct SlackConfig {
+    #[serde(rename = "hook-url")]
+    hook_url: String,
+    channel: Option<String>,
+    username: Option<String>,
+    #[serde(rename = "icon-emoji")]
+    icon_emoji: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SlackPayload<'a> {
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: &'a Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: &'a Option<String>,
+    #[serde(rename = "icon_emoji", skip_serializing_if = "Option::is_none")]
+    icon_emoji: &'a Option<String>,
+}
+
+pub struct SlackNotifier {
+    config: SlackConfig,
+    client: reqwest::blocking::Client,
+}
+
+impl SlackNotifier {
+    pub fn new(config: SlackConfig) -> NasResult<Self> {
+        Ok(SlackNotifier {
+            config,
+            client: http_client()?,
+        })
+    }
+}
+
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &str {
+        "slack"
+    }
+    fn send(&self, text: &str) -> NasResult<()> {
+        let payload = SlackPayload {
+            text,
+            channel: &self.config.channel,
+            username: &self.config.username,
+            icon_emoji: &self.config.icon_emoji,
+        };
+        self.client
+            .post(&self.config.hook_url)
+            .json(&payload)
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use rusoto_core::{HttpClient, Region};
+use rusoto_credential::StaticProvider;
+use rusoto_sns::{PublishInput, Sns, SnsClient};
+use serde::Deserialize;
+use tokio::runtime::Runtime;
+
+use crate::NasResult;
+
+use super::Notifier;
+
+#[derive(Deserialize, Clone)]
+pub struct SnsConfig {
+    key: String,
+    secret: String,
+    region: String,
+    phone: Option<String>,
+    #[serde(rename = "topic-arn")]
+    topic_arn: Option<String>,
+}
+
+pub struct SnsNotifier {
+    config: SnsConfig,
+    client: SnsClient,
+    runtime: Runtime,
+}
+
+impl SnsNotifier {
+    pub fn new(config: SnsConfig) -> NasResult<Self> {
+        let region: Region = config.region.parse()?;
+        // Each notifier carries its own credentials instead of going through the env-var-based
+        // default provider, so multiple `Sns` channels (e.g. different accounts) don't stomp on
+        // each other's global process state.
+        let credentials = StaticProvider::new_minimal(config.key.clone(), config.secret.clone());
+        let client = SnsClient::new_with(HttpClient::new()?, credentials, region);
+        let runtime = Runtime::new()?;
+        Ok(SnsNotifier {
+            config,
+            client,
+            runtime,
+        })
+    }
+}
+
+impl Notifier for SnsNotifier {
+    fn name(&self) -> &str {
+        "sns"
+    }
+    fn send(&self, text: &str) -> NasResult<()> {
+        let input = PublishInput {
+            message: text.to_owned(),
+            phone_number: self.config.phone.clone(),
+            topic_arn: self.config.topic_arn.clone(),
+            ..Default::default()
+        };
+        self.runtime.block_on(self.client.publish(input))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::NasResult;
+
+use super::{http_client, Notifier};
+
+#[derive(Deserialize, Clone)]
+pub struct TelegramConfig {
+    #[serde(rename = "user-id")]
+    user_id: i64,
+    #[serde(rename = "api-key")]
+    api_key: String,
+}
+
+pub struct TelegramNotifier {
+    config: TelegramConfig,
+    client: reqwest::blocking::Client,
+}
+
+impl TelegramNotifier {
+    pub fn new(config: TelegramConfig) -> NasResult<Self> {
+        Ok(TelegramNotifier {
+            config,
+            client: http_client()?,
+        })
+    }
+}
+
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+    fn send(&self, text: &str) -> NasResult<()> {
+        let url = format!(
+            "https://api.telegram.org/bot{}/sendMessage",
+            self.config.api_key
+        );
+        let mut payload = HashMap::new();
+        let user_id = self.config.user_id.to_string();
+        payload.insert("chat_id", user_id.as_str());
+        payload.insert("text", text);
+        payload.insert("parse_mode", "Markdown");
+        self.client
+            .post(url)
+            .json(&payload)
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// User-supplied message templates, one per event type. Each template may reference
+/// placeholders like `{hostname}` or `{ip}`; unknown placeholders are left untouched. Any
+/// template left unset falls back to the built-in wording for that event.
+#[derive(Deserialize, Clone, Default)]
+pub struct TemplatesConfig {
+    #[serde(rename = "new-login-ip")]
+    pub new_login_ip: Option<String>,
+    #[serde(rename = "failed-login")]
+    pub failed_login: Option<String>,
+    #[serde(rename = "pool-health-change")]
+    pub pool_health_change: Option<String>,
+    #[serde(rename = "pool-recovered")]
+    pub pool_recovered: Option<String>,
+}
+
+const DEFAULT_NEW_LOGIN_IP: &str = "There was a successful login on `{hostname}` from an unknown IP address ({ip}). Here's the relevant line:\n\n`{logline}`";
+const DEFAULT_FAILED_LOGIN: &str = "There were {count} failed login attempt(s) from {ip_count} IP address(es) on `{hostname}` in the last {window_seconds} seconds.";
+const DEFAULT_POOL_HEALTH_CHANGE: &str = "Zpool `{pool}` entered the `{new_state}` state.";
+const DEFAULT_POOL_RECOVERED: &str = "Zpool `{pool}` recovered and is now `{new_state}`.";
+
+/// Substitute `{name}` tokens in `template` with the corresponding value from `vars`. Tokens
+/// with no matching entry in `vars` are left in place untouched.
+pub fn render(template: &str, vars: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            out.push_str(rest);
+            return out;
+        };
+        let end = start + end;
+        let name = &rest[start + 1..end];
+        out.push_str(&rest[..start]);
+        match vars.get(name) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_known_tokens() {
+        let vars = HashMap::from([("hostname", "nas1".to_string()), ("ip", "203.0.113.1".to_string())]);
+        assert_eq!(
+            render("login from {ip} on {hostname}", &vars),
+            "login from 203.0.113.1 on nas1"
+        );
+    }
+
+    #[test]
+    fn leaves_unknown_tokens_untouched() {
+        let vars = HashMap::from([("hostname", "nas1".to_string())]);
+        assert_eq!(
+            render("{hostname}: {unknown}", &vars),
+            "nas1: {unknown}"
+        );
+    }
+
+    #[test]
+    fn leaves_empty_token_untouched() {
+        let vars: HashMap<&str, String> = HashMap::new();
+        assert_eq!(render("before {} after", &vars), "before {} after");
+    }
+
+    #[test]
+    fn falls_back_on_unmatched_brace() {
+        let vars = HashMap::from([("hostname", "nas1".to_string())]);
+        assert_eq!(
+            render("{hostname} has an unmatched {brace", &vars),
+            "nas1 has an unmatched {brace"
+        );
+    }
+
+    #[test]
+    fn renders_plain_text_with_no_tokens() {
+        let vars: HashMap<&str, String> = HashMap::new();
+        assert_eq!(render("no tokens here", &vars), "no tokens here");
+    }
+}
+
+impl TemplatesConfig {
+    pub fn new_login_ip(&self, vars: &HashMap<&str, String>) -> String {
+        render(
+            self.new_login_ip.as_deref().unwrap_or(DEFAULT_NEW_LOGIN_IP),
+            vars,
+        )
+    }
+    pub fn failed_login(&self, vars: &HashMap<&str, String>) -> String {
+        render(
+            self.failed_login.as_deref().unwrap_or(DEFAULT_FAILED_LOGIN),
+            vars,
+        )
+    }
+    pub fn pool_health_change(&self, vars: &HashMap<&str, String>) -> String {
+        render(
+            self.pool_health_change
+                .as_deref()
+                .unwrap_or(DEFAULT_POOL_HEALTH_CHANGE),
+            vars,
+        )
+    }
+    pub fn pool_recovered(&self, vars: &HashMap<&str, String>) -> String {
+        render(
+            self.pool_recovered
+                .as_deref()
+                .unwrap_or(DEFAULT_POOL_RECOVERED),
+            vars,
+        )
+    }
+}